@@ -16,7 +16,7 @@ const SUGGESTIONS_BY_EXTENSION_ID: &[(&str, &[&str])] = &[
     ("beancount", &["beancount"]),
     ("clojure", &["bb", "clj", "cljc", "cljs", "edn"]),
     ("neocmake", &["CMakeLists.txt", "cmake"]),
-    ("csharp", &["cs"]),
+    ("csharp", &["cs", "csx"]),
     ("cython", &["pyx", "pxd", "pxi"]),
     ("dart", &["dart"]),
     ("dockerfile", &["Dockerfile"]),
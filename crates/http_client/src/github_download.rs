@@ -7,7 +7,7 @@ use std::{
 use anyhow::{Context, Result};
 use async_compression::futures::bufread::GzipDecoder;
 use futures::{AsyncRead, AsyncSeek, AsyncSeekExt, AsyncWrite, io::BufReader};
-use sha2::{Digest, Sha256};
+use sha2::{Digest, Sha256, Sha512};
 
 use crate::{HttpClient, github::AssetKind};
 
@@ -15,9 +15,44 @@ use crate::{HttpClient, github::AssetKind};
 pub struct GithubBinaryMetadata {
     pub metadata_version: u64,
     pub digest: Option<String>,
+    /// The algorithm of `digest`, SHA-256 when absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub digest_algorithm: Option<DigestAlgorithm>,
 }
 
 impl GithubBinaryMetadata {
+    /// Metadata of an asset downloaded with the given expected digest, recording the digest's
+    /// algorithm separately from its value.
+    pub fn for_digest(digest: Option<String>) -> Self {
+        let parsed = digest
+            .as_deref()
+            .and_then(parse_digest)
+            .map(|(algorithm, hex)| (algorithm, hex.to_string()));
+        match parsed {
+            Some((algorithm, hex)) => Self {
+                metadata_version: 1,
+                digest: Some(hex),
+                digest_algorithm: Some(algorithm),
+            },
+            None => Self {
+                metadata_version: 1,
+                digest,
+                digest_algorithm: None,
+            },
+        }
+    }
+
+    /// Whether the recorded digest is the given expected one, which is either a bare SHA-256 hex
+    /// string or tagged with its algorithm as in `sha512:<hex>`. Digests of different algorithms
+    /// never match, since the asset can't be rehashed after it has been extracted.
+    pub fn matches_digest(&self, expected: &str) -> bool {
+        let (Some(digest), Some((algorithm, hex))) = (&self.digest, parse_digest(expected)) else {
+            return false;
+        };
+        self.digest_algorithm.unwrap_or(DigestAlgorithm::Sha256) == algorithm
+            && digest.eq_ignore_ascii_case(hex)
+    }
+
     pub async fn read_from_file(metadata_path: &Path) -> Result<GithubBinaryMetadata> {
         let metadata_content = async_fs::read_to_string(metadata_path)
             .await
@@ -42,6 +77,27 @@ pub async fn download_server_binary(
     digest: Option<&str>,
     destination_path: &Path,
     asset_kind: AssetKind,
+) -> Result<(), anyhow::Error> {
+    download_server_binary_with_progress(
+        http_client,
+        url,
+        digest,
+        destination_path,
+        asset_kind,
+        |_, _| {},
+    )
+    .await
+}
+
+/// Like [`download_server_binary`], calling `on_progress` with the number of bytes downloaded so
+/// far and the total size, when the server reports one, as the asset is being downloaded.
+pub async fn download_server_binary_with_progress(
+    http_client: &dyn HttpClient,
+    url: &str,
+    digest: Option<&str>,
+    destination_path: &Path,
+    asset_kind: AssetKind,
+    on_progress: impl FnMut(u64, Option<u64>) + Unpin,
 ) -> Result<(), anyhow::Error> {
     log::info!("downloading github artifact from {url}");
     let Some(destination_parent) = destination_path.parent() else {
@@ -53,7 +109,16 @@ pub async fn download_server_binary(
         .get(url, Default::default(), true)
         .await
         .with_context(|| format!("downloading release from {url}"))?;
-    let body = response.body_mut();
+    let total = response
+        .headers()
+        .get(http::header::CONTENT_LENGTH)
+        .and_then(|length| length.to_str().ok()?.parse().ok());
+    let body = ProgressReader {
+        reader: response.body_mut(),
+        read: 0,
+        total,
+        on_progress,
+    };
 
     if let Err(err) = extract_to_staging(body, digest, url, &staging_path, asset_kind).await {
         cleanup_staging_path(&staging_path, asset_kind).await;
@@ -76,25 +141,32 @@ async fn extract_to_staging(
     asset_kind: AssetKind,
 ) -> Result<()> {
     match digest {
-        Some(expected_sha_256) => {
+        Some(digest) => {
+            let (algorithm, expected_digest) = parse_digest(digest)
+                .with_context(|| format!("unsupported digest {digest:?} for {url}"))?;
             let temp_asset_file = tempfile::NamedTempFile::new()
                 .with_context(|| format!("creating a temporary file for {url}"))?;
             let (temp_asset_file, _temp_guard) = temp_asset_file.into_parts();
             let mut writer = HashingWriter {
                 writer: async_fs::File::from(temp_asset_file),
-                hasher: Sha256::new(),
+                hasher: AssetHasher::new(algorithm),
             };
             futures::io::copy(&mut BufReader::new(body), &mut writer)
                 .await
                 .with_context(|| {
                     format!("saving archive contents into the temporary file for {url}")
                 })?;
-            let asset_sha_256 = format!("{:x}", writer.hasher.finalize());
+            let asset_digest = writer.hasher.finalize_hex();
 
-            anyhow::ensure!(
-                asset_sha_256 == expected_sha_256,
-                "{url} asset got SHA-256 mismatch. Expected: {expected_sha_256}, Got: {asset_sha_256}",
-            );
+            if !asset_digest.eq_ignore_ascii_case(expected_digest) {
+                return Err(DigestMismatchError {
+                    url: url.to_string(),
+                    algorithm,
+                    expected: expected_digest.to_string(),
+                    actual: asset_digest,
+                }
+                .into());
+            }
             writer
                 .writer
                 .seek(std::io::SeekFrom::Start(0))
@@ -117,6 +189,99 @@ async fn extract_to_staging(
     Ok(())
 }
 
+/// The downloaded asset doesn't have the expected digest, which downloading it again won't fix.
+#[derive(Debug)]
+pub struct DigestMismatchError {
+    url: String,
+    algorithm: DigestAlgorithm,
+    expected: String,
+    actual: String,
+}
+
+impl std::fmt::Display for DigestMismatchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} asset got {} mismatch. Expected: {}, Got: {}",
+            self.url, self.algorithm, self.expected, self.actual
+        )
+    }
+}
+
+impl std::error::Error for DigestMismatchError {}
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Deserialize, serde::Serialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum DigestAlgorithm {
+    Sha256,
+    Sha512,
+}
+
+impl std::fmt::Display for DigestAlgorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Sha256 => write!(f, "SHA-256"),
+            Self::Sha512 => write!(f, "SHA-512"),
+        }
+    }
+}
+
+/// Parses an asset digest into its algorithm and hex value.
+///
+/// Digests are either bare SHA-256 hex strings (GitHub's `sha256:` prefix is stripped when
+/// fetching releases) or tagged with their algorithm, as in `sha512:<hex>`. When several
+/// comma-separated digests are given, the strongest supported one is used.
+fn parse_digest(digest: &str) -> Option<(DigestAlgorithm, &str)> {
+    digest
+        .split(',')
+        .filter_map(|digest| {
+            let digest = digest.trim();
+            let (algorithm, hex) = match digest.split_once(':') {
+                Some(("sha256", hex)) => (DigestAlgorithm::Sha256, hex),
+                Some(("sha512", hex)) => (DigestAlgorithm::Sha512, hex),
+                Some(_) => return None,
+                None => (DigestAlgorithm::Sha256, digest),
+            };
+            let expected_len = match algorithm {
+                DigestAlgorithm::Sha256 => 64,
+                DigestAlgorithm::Sha512 => 128,
+            };
+            (hex.len() == expected_len && hex.chars().all(|c| c.is_ascii_hexdigit()))
+                .then_some((algorithm, hex))
+        })
+        .max_by_key(|(algorithm, _)| *algorithm)
+}
+
+enum AssetHasher {
+    Sha256(Sha256),
+    Sha512(Sha512),
+}
+
+impl AssetHasher {
+    fn new(algorithm: DigestAlgorithm) -> Self {
+        match algorithm {
+            DigestAlgorithm::Sha256 => Self::Sha256(Sha256::new()),
+            DigestAlgorithm::Sha512 => Self::Sha512(Sha512::new()),
+        }
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        match self {
+            Self::Sha256(hasher) => hasher.update(data),
+            Self::Sha512(hasher) => hasher.update(data),
+        }
+    }
+
+    fn finalize_hex(self) -> String {
+        match self {
+            Self::Sha256(hasher) => format!("{:x}", hasher.finalize()),
+            Self::Sha512(hasher) => format!("{:x}", hasher.finalize()),
+        }
+    }
+}
+
 fn staging_path(parent: &Path, asset_kind: AssetKind) -> Result<PathBuf> {
     match asset_kind {
         AssetKind::TarGz | AssetKind::Zip => {
@@ -231,7 +396,7 @@ async fn extract_gz(
 
 struct HashingWriter<W: AsyncWrite + Unpin> {
     writer: W,
-    hasher: Sha256,
+    hasher: AssetHasher,
 }
 
 impl<W: AsyncWrite + Unpin> AsyncWrite for HashingWriter<W> {
@@ -263,3 +428,75 @@ impl<W: AsyncWrite + Unpin> AsyncWrite for HashingWriter<W> {
         Pin::new(&mut self.writer).poll_close(cx)
     }
 }
+
+struct ProgressReader<R, F> {
+    reader: R,
+    read: u64,
+    total: Option<u64>,
+    on_progress: F,
+}
+
+impl<R: AsyncRead + Unpin, F: FnMut(u64, Option<u64>) + Unpin> AsyncRead for ProgressReader<R, F> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+        let this = &mut *self;
+        let poll = Pin::new(&mut this.reader).poll_read(cx, buf);
+        if let Poll::Ready(Ok(n)) = &poll
+            && *n > 0
+        {
+            this.read += *n as u64;
+            (this.on_progress)(this.read, this.total);
+        }
+        poll
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DigestAlgorithm, GithubBinaryMetadata, parse_digest};
+
+    #[test]
+    fn test_parse_digest() {
+        let sha_256 = "a".repeat(64);
+        let sha_512 = "B".repeat(128);
+
+        assert_eq!(
+            parse_digest(&sha_256),
+            Some((DigestAlgorithm::Sha256, sha_256.as_str()))
+        );
+        assert_eq!(
+            parse_digest(&format!("sha512:{sha_512}")),
+            Some((DigestAlgorithm::Sha512, sha_512.as_str()))
+        );
+        assert_eq!(
+            parse_digest(&format!("sha256:{sha_256}, sha512:{sha_512}")),
+            Some((DigestAlgorithm::Sha512, sha_512.as_str()))
+        );
+        assert_eq!(parse_digest(&format!("md5:{sha_256}")), None);
+        assert_eq!(parse_digest(&format!("sha512:{sha_256}")), None);
+    }
+
+    #[test]
+    fn test_metadata_digest_algorithm() {
+        let sha_256 = "a".repeat(64);
+        let sha_512 = "b".repeat(128);
+
+        let metadata = GithubBinaryMetadata::for_digest(Some(format!("sha512:{sha_512}")));
+        assert_eq!(metadata.digest.as_deref(), Some(sha_512.as_str()));
+        assert_eq!(metadata.digest_algorithm, Some(DigestAlgorithm::Sha512));
+        assert!(metadata.matches_digest(&format!("sha512:{}", sha_512.to_uppercase())));
+        assert!(!metadata.matches_digest(&sha_512));
+
+        // Metadata written before the algorithm was recorded holds bare SHA-256 digests.
+        let legacy: GithubBinaryMetadata =
+            serde_json::from_str(&format!(r#"{{"metadata_version":1,"digest":"{sha_256}"}}"#))
+                .unwrap();
+        assert!(legacy.matches_digest(&sha_256));
+        assert!(legacy.matches_digest(&format!("sha256:{sha_256}")));
+        assert!(!legacy.matches_digest(&format!("sha512:{sha_512}")));
+        assert!(!GithubBinaryMetadata::for_digest(None).matches_digest(&sha_256));
+    }
+}
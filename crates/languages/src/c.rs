@@ -134,6 +134,7 @@ impl LspInstaller for CLspAdapter {
             &GithubBinaryMetadata {
                 metadata_version: 1,
                 digest: expected_digest,
+                digest_algorithm: None,
             },
             &metadata_path,
         )
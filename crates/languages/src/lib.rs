@@ -89,8 +89,9 @@ pub fn init(languages: Arc<LanguageRegistry>, fs: Arc<dyn Fs>, node: NodeRuntime
     ]);
 
     let c_lsp_adapter = Arc::new(c::CLspAdapter);
-    let csharp_lsp_adapter = Arc::new(csharp::CsharpLspAdapter);
+    let csharp_lsp_adapter = Arc::new(csharp::CsharpLspAdapter::default());
     let csharp_context_provider = Arc::new(csharp::CsharpContextProvider);
+    let csharp_toolchain_provider = Arc::new(csharp::CsharpToolchainProvider);
     let css_lsp_adapter = Arc::new(css::CssLspAdapter::new(node.clone()));
     let eslint_adapter = Arc::new(eslint::EsLintLspAdapter::new(node.clone()));
     let go_context_provider = Arc::new(go::GoContextProvider);
@@ -137,6 +138,7 @@ pub fn init(languages: Arc<LanguageRegistry>, fs: Arc<dyn Fs>, node: NodeRuntime
             name: "csharp",
             adapters: vec![csharp_lsp_adapter.clone()],
             context: Some(csharp_context_provider),
+            toolchain: Some(csharp_toolchain_provider),
             ..Default::default()
         },
         LanguageInfo {
@@ -335,6 +337,8 @@ pub fn init(languages: Arc<LanguageRegistry>, fs: Arc<dyn Fs>, node: NodeRuntime
         languages.register_lsp_adapter(language.into(), eslint_adapter.clone());
     }
 
+    csharp::observe_settings(cx);
+
     let mut subscription = languages.subscribe();
     let mut prev_language_settings = languages.language_settings();
 
@@ -360,9 +364,10 @@ pub fn init(languages: Arc<LanguageRegistry>, fs: Arc<dyn Fs>, node: NodeRuntime
         anyhow::Ok(())
     })
     .detach();
-    let manifest_providers: [Arc<dyn ManifestProvider>; 2] = [
+    let manifest_providers: [Arc<dyn ManifestProvider>; 3] = [
         Arc::from(CargoManifestProvider),
         Arc::from(PyprojectTomlManifestProvider),
+        Arc::from(csharp::GlobalJsonManifestProvider),
     ];
     for provider in manifest_providers {
         project::ManifestProvidersStore::global(cx).register(provider);
@@ -420,6 +420,7 @@ impl LspInstaller for TyLspAdapter {
             &GithubBinaryMetadata {
                 metadata_version: 1,
                 digest: expected_digest,
+                digest_algorithm: None,
             },
             &metadata_path,
         )
@@ -2603,6 +2604,7 @@ impl LspInstaller for RuffLspAdapter {
             &GithubBinaryMetadata {
                 metadata_version: 1,
                 digest: expected_digest,
+                digest_algorithm: None,
             },
             &metadata_path,
         )
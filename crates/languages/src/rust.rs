@@ -764,6 +764,7 @@ impl LspInstaller for RustLspAdapter {
             &GithubBinaryMetadata {
                 metadata_version: 1,
                 digest: expected_digest,
+                digest_algorithm: None,
             },
             &metadata_path,
         )
@@ -335,6 +335,27 @@ pub struct LocalLspStore {
     _background_diagnostics_worker: Shared<Task<()>>,
 }
 
+/// Checks that a language server binary configured with `binary.path` can be started, to report a
+/// misconfigured path clearly instead of as a failure to spawn the server.
+pub async fn validate_user_provided_binary(path: &Path) -> Result<()> {
+    let metadata = smol::fs::metadata(path)
+        .await
+        .with_context(|| format!("language server binary {path:?} from `binary.path` not found"))?;
+    anyhow::ensure!(
+        metadata.is_file(),
+        "language server binary {path:?} from `binary.path` is not a file"
+    );
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        anyhow::ensure!(
+            metadata.permissions().mode() & 0o111 != 0,
+            "language server binary {path:?} from `binary.path` is not executable"
+        );
+    }
+    Ok(())
+}
+
 impl LocalLspStore {
     /// Returns the running language server for the given ID. Note if the language server is starting, it will not be returned.
     pub fn running_language_server_for_id(
@@ -709,11 +730,16 @@ impl LocalLspStore {
                     languages
                         .update_lsp_binary_status(adapter.name(), BinaryStatus::Starting);
                 }
+                let path = delegate.resolve_relative_path(path);
+                // Bare command names are looked up in `$PATH` when spawning the server.
+                if path.is_absolute() {
+                    validate_user_provided_binary(&path).await?;
+                }
                 let mut env = delegate.shell_env().await;
                 env.extend(settings.env.unwrap_or_default());
 
                 Ok(LanguageServerBinary {
-                    path: delegate.resolve_relative_path(path),
+                    path,
                     env: Some(env),
                     arguments: settings
                         .arguments
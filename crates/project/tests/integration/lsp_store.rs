@@ -72,3 +72,33 @@ fn test_trailing_newline_in_completion_documentation() {
         CompletionDocumentation::SingleLine(s) if s == "some value"
     ));
 }
+
+#[test]
+fn test_validate_user_provided_binary() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let binary = temp_dir.path().join("language-server");
+    std::fs::write(&binary, "#!/bin/sh\n").unwrap();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&binary, std::fs::Permissions::from_mode(0o755)).unwrap();
+    }
+    smol::block_on(validate_user_provided_binary(&binary)).unwrap();
+
+    let missing = temp_dir.path().join("missing");
+    let error = smol::block_on(validate_user_provided_binary(&missing)).unwrap_err();
+    assert!(error.to_string().contains("not found"), "{error:#}");
+
+    let error = smol::block_on(validate_user_provided_binary(temp_dir.path())).unwrap_err();
+    assert!(error.to_string().contains("is not a file"), "{error:#}");
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let not_executable = temp_dir.path().join("not-executable");
+        std::fs::write(&not_executable, "").unwrap();
+        std::fs::set_permissions(&not_executable, std::fs::Permissions::from_mode(0o644)).unwrap();
+        let error = smol::block_on(validate_user_provided_binary(&not_executable)).unwrap_err();
+        assert!(error.to_string().contains("is not executable"), "{error:#}");
+    }
+}
@@ -93,7 +93,7 @@ const FILE_SUFFIXES_BY_ICON_KEY: &[(&str, &[&str])] = &[
         ],
     ),
     ("crystal", &["cr", "ecr"]),
-    ("csharp", &["cs"]),
+    ("csharp", &["cs", "csx"]),
     ("csproj", &["csproj"]),
     ("css", &["css", "pcss", "postcss"]),
     ("cue", &["cue"]),